@@ -15,12 +15,64 @@ mod todo {
         pub id: u32,
         pub description: String,
         pub completed: bool,
+        pub owner: AccountId,
+        pub due_block: Option<u32>,
+    }
+
+    /// A read-only view of a task with its overdue status computed against the current
+    /// block, without storing that status on-chain.
+    #[derive(Debug, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct TaskView {
+        pub id: u32,
+        pub description: String,
+        pub completed: bool,
+        pub owner: AccountId,
+        pub due_block: Option<u32>,
+        pub is_overdue: bool,
     }
 
     #[ink(storage)]
     pub struct Todo {
-        tasks: Vec<Task>,
+        tasks: ink::storage::Mapping<u32, Task>,
+        ids: Vec<u32>,
+        /// Maps a task id to its index in `ids`, so `remove_task` can `swap_remove`
+        /// instead of scanning and shifting the whole index.
+        positions: ink::storage::Mapping<u32, u32>,
         next_id: u32,
+        admin: AccountId,
+    }
+
+    /// Errors that can occur while interacting with the contract.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// No task exists for the given id.
+        NotFound,
+        /// The caller is neither the task's owner nor the contract admin.
+        NotAuthorized,
+    }
+
+    /// Emitted when a new task is added.
+    #[ink(event)]
+    pub struct TaskAdded {
+        #[ink(topic)]
+        id: u32,
+        description: String,
+    }
+
+    /// Emitted when a task is marked as completed.
+    #[ink(event)]
+    pub struct TaskCompleted {
+        #[ink(topic)]
+        id: u32,
+    }
+
+    /// Emitted when a task is removed.
+    #[ink(event)]
+    pub struct TaskRemoved {
+        #[ink(topic)]
+        id: u32,
     }
 
     impl Default for Todo {
@@ -33,52 +85,141 @@ mod todo {
         #[ink(constructor)]
         pub fn new() -> Self {
             Self {
-                tasks: Vec::new(),
+                tasks: ink::storage::Mapping::default(),
+                ids: Vec::new(),
+                positions: ink::storage::Mapping::default(),
                 next_id: 0,
+                admin: Self::env().caller(),
             }
         }
 
         #[ink(message)]
         pub fn add_task(&mut self, description: String) -> u32 {
-            let id = self.next_id;
-            let task = Task {
-                id,
-                description,
-                completed: false,
-            };
-            self.tasks.push(task);
-            self.next_id = self.next_id.saturating_add(1);
-            id
+            self.insert_task(description, None)
         }
 
+        /// Adds a task with a deadline expressed as a block number. Use `overdue_tasks`
+        /// to find tasks whose `due_block` has already passed.
         #[ink(message)]
-        pub fn complete_task(&mut self, id: u32) -> bool {
-            if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
-                task.completed = true;
-                true
-            } else {
-                false
-            }
+        pub fn add_task_with_deadline(&mut self, description: String, due_block: u32) -> u32 {
+            self.insert_task(description, Some(due_block))
         }
 
         #[ink(message)]
-        pub fn remove_task(&mut self, id: u32) -> bool {
-            if let Some(pos) = self.tasks.iter().position(|t| t.id == id) {
-                self.tasks.remove(pos);
-                true
-            } else {
-                false
+        pub fn complete_task(&mut self, id: u32) -> Result<(), Error> {
+            let mut task = self.tasks.get(id).ok_or(Error::NotFound)?;
+            self.ensure_authorized(&task)?;
+            task.completed = true;
+            self.tasks.insert(id, &task);
+            self.env().emit_event(TaskCompleted { id });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn remove_task(&mut self, id: u32) -> Result<(), Error> {
+            let task = self.tasks.get(id).ok_or(Error::NotFound)?;
+            self.ensure_authorized(&task)?;
+            let pos = self.positions.get(id).ok_or(Error::NotFound)?;
+
+            self.ids.swap_remove(pos as usize);
+            if let Some(&moved_id) = self.ids.get(pos as usize) {
+                self.positions.insert(moved_id, &pos);
             }
+            self.positions.remove(id);
+            self.tasks.remove(id);
+            self.env().emit_event(TaskRemoved { id });
+            Ok(())
         }
 
         #[ink(message)]
         pub fn get_tasks(&self) -> Vec<Task> {
-            self.tasks.clone()
+            self.ids
+                .iter()
+                .filter_map(|id| self.tasks.get(id))
+                .collect()
         }
 
         #[ink(message)]
         pub fn get_task(&self, id: u32) -> Option<Task> {
-            self.tasks.iter().find(|t| t.id == id).cloned()
+            self.tasks.get(id)
+        }
+
+        /// Returns only the tasks owned by the caller.
+        #[ink(message)]
+        pub fn get_my_tasks(&self) -> Vec<Task> {
+            let caller = self.env().caller();
+            self.ids
+                .iter()
+                .filter_map(|id| self.tasks.get(id))
+                .filter(|task| task.owner == caller)
+                .collect()
+        }
+
+        /// Upgrades the contract's code while preserving its storage, so a bug fix or a
+        /// new field doesn't require redeploying (and losing) the stored tasks.
+        ///
+        /// Only the admin set in `new()` may trigger an upgrade.
+        #[ink(message)]
+        pub fn set_code(&mut self, code_hash: Hash) -> Result<(), Error> {
+            if self.env().caller() != self.admin {
+                return Err(Error::NotAuthorized);
+            }
+            self.env().set_code_hash(&code_hash).unwrap_or_else(|err| {
+                panic!("failed to set code hash: {err:?}")
+            });
+            Ok(())
+        }
+
+        /// Checks that the caller is either the task's owner or the contract admin.
+        fn ensure_authorized(&self, task: &Task) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if caller == task.owner || caller == self.admin {
+                Ok(())
+            } else {
+                Err(Error::NotAuthorized)
+            }
+        }
+
+        /// Returns every task as a `TaskView` whose `is_overdue` is set when its `due_block`
+        /// has already passed, as computed against the current block number. Completed
+        /// tasks are never considered overdue.
+        #[ink(message)]
+        pub fn overdue_tasks(&self) -> Vec<TaskView> {
+            let current_block = self.env().block_number();
+            self.ids
+                .iter()
+                .filter_map(|id| self.tasks.get(id))
+                .map(|task| {
+                    let is_overdue = !task.completed
+                        && task.due_block.is_some_and(|due| due < current_block);
+                    TaskView {
+                        id: task.id,
+                        description: task.description,
+                        completed: task.completed,
+                        owner: task.owner,
+                        due_block: task.due_block,
+                        is_overdue,
+                    }
+                })
+                .collect()
+        }
+
+        fn insert_task(&mut self, description: String, due_block: Option<u32>) -> u32 {
+            let id = self.next_id;
+            let task = Task {
+                id,
+                description: description.clone(),
+                completed: false,
+                owner: self.env().caller(),
+                due_block,
+            };
+            self.tasks.insert(id, &task);
+            let pos = self.ids.len() as u32;
+            self.ids.push(id);
+            self.positions.insert(id, &pos);
+            self.next_id = self.next_id.saturating_add(1);
+            self.env().emit_event(TaskAdded { id, description });
+            id
         }
     }
 
@@ -112,7 +253,7 @@ mod todo {
             let mut todo = Todo::new();
             let id = todo.add_task(String::from("Buy milk"));
 
-            assert!(todo.complete_task(id));
+            assert_eq!(todo.complete_task(id), Ok(()));
             let task = todo.get_task(id).unwrap();
             assert_eq!(task.completed, true);
         }
@@ -122,31 +263,165 @@ mod todo {
             let mut todo = Todo::new();
             let id = todo.add_task(String::from("Buy milk"));
 
-            assert!(todo.remove_task(id));
+            assert_eq!(todo.remove_task(id), Ok(()));
             assert_eq!(todo.get_tasks().len(), 0);
             assert!(todo.get_task(id).is_none());
         }
+
+        #[ink::test]
+        fn remove_task_swap_removes_without_losing_siblings() {
+            let mut todo = Todo::new();
+            let first = todo.add_task(String::from("First"));
+            let second = todo.add_task(String::from("Second"));
+            let third = todo.add_task(String::from("Third"));
+
+            // Removing the middle id exercises the swap_remove + position-map update.
+            assert_eq!(todo.remove_task(second), Ok(()));
+            assert_eq!(todo.get_tasks().len(), 2);
+            assert!(todo.get_task(second).is_none());
+            assert!(todo.get_task(first).is_some());
+            assert!(todo.get_task(third).is_some());
+
+            // The id that was swapped into `second`'s slot must still be removable.
+            assert_eq!(todo.remove_task(third), Ok(()));
+            assert_eq!(todo.get_tasks().len(), 1);
+            assert!(todo.get_task(first).is_some());
+        }
+
+        #[ink::test]
+        fn complete_task_rejects_missing_id() {
+            let mut todo = Todo::new();
+            assert_eq!(todo.complete_task(42), Err(Error::NotFound));
+        }
+
+        #[ink::test]
+        fn complete_task_rejects_non_owner() {
+            let mut todo = Todo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let id = todo.add_task(String::from("Buy milk"));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(todo.complete_task(id), Err(Error::NotAuthorized));
+        }
+
+        #[ink::test]
+        fn admin_can_complete_anyones_task() {
+            let mut todo = Todo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let id = todo.add_task(String::from("Bob's task"));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(todo.complete_task(id), Ok(()));
+        }
+
+        #[ink::test]
+        fn remove_task_rejects_missing_id() {
+            let mut todo = Todo::new();
+            assert_eq!(todo.remove_task(42), Err(Error::NotFound));
+        }
+
+        #[ink::test]
+        fn remove_task_rejects_non_owner() {
+            let mut todo = Todo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let id = todo.add_task(String::from("Buy milk"));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(todo.remove_task(id), Err(Error::NotAuthorized));
+        }
+
+        #[ink::test]
+        fn admin_can_remove_anyones_task() {
+            let mut todo = Todo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let id = todo.add_task(String::from("Bob's task"));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(todo.remove_task(id), Ok(()));
+        }
+
+        #[ink::test]
+        fn get_my_tasks_filters_by_caller() {
+            let mut todo = Todo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            todo.add_task(String::from("Alice's task"));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            todo.add_task(String::from("Bob's task"));
+            assert_eq!(todo.get_my_tasks().len(), 1);
+            assert_eq!(todo.get_my_tasks()[0].owner, accounts.bob);
+        }
+
+        #[ink::test]
+        fn set_code_rejects_non_admin() {
+            let mut todo = Todo::new();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            let result = todo.set_code(Hash::from([0x42; 32]));
+            assert_eq!(result, Err(Error::NotAuthorized));
+        }
+
+        #[ink::test]
+        fn events_are_emitted() {
+            let mut todo = Todo::new();
+            let id = todo.add_task(String::from("Buy milk"));
+            todo.complete_task(id).unwrap();
+            todo.remove_task(id).unwrap();
+
+            let events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 3);
+        }
+
+        #[ink::test]
+        fn overdue_tasks_works() {
+            let mut todo = Todo::new();
+            let current_block = ink::env::block_number::<ink::env::DefaultEnvironment>();
+            let id = todo.add_task_with_deadline(String::from("Buy milk"), current_block);
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+
+            let overdue = todo.overdue_tasks();
+            let task = overdue.iter().find(|t| t.id == id).unwrap();
+            assert!(task.is_overdue);
+
+            todo.complete_task(id).unwrap();
+            let overdue = todo.overdue_tasks();
+            let task = overdue.iter().find(|t| t.id == id).unwrap();
+            assert!(!task.is_overdue, "completed tasks are never overdue");
+        }
     }
 
 
     /// This is how you'd write end-to-end (E2E) or integration tests for ink! contracts.
     ///
-    /// When running these you need to make sure that you:
+    /// The test bodies are generic over `E2EBackend`. The shared add-and-complete body runs
+    /// against both an in-process `drink!` sandbox (fast, no external process) and a live
+    /// `substrate-contracts-node`; the other tests run on the sandbox only:
     /// - Compile the tests with the e2e-tests feature flag enabled (--features e2e-tests)
-    /// - Are running a Substrate node which contains pallet-contracts in the background
+    /// - The `runtime_only` backend needs nothing else running
+    /// - The default (node) backend still requires a Substrate node with pallet-contracts
     #[cfg(all(test, feature = "e2e-tests"))]
     mod e2e_tests {
         /// Imports all the definitions from the outer scope so we can use them here.
         use super::*;
 
         /// A helper function used for calling contract messages.
-        use ink_e2e::ContractsBackend;
+        use ink_e2e::{ContractsBackend, E2EBackend};
 
         /// The End-to-End test Result type.
         type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
-        #[ink_e2e::test]
-        async fn e2e_add_and_complete_task(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+        /// Shared test body, generic over the backend so it can run against either an
+        /// in-memory `drink!` sandbox or a live node without duplicating assertions.
+        async fn e2e_add_and_complete_task<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
             let mut constructor = TodoRef::new();
             let contract = client
                 .instantiate("todo", &ink_e2e::alice(), &mut constructor)
@@ -163,6 +438,7 @@ mod todo {
                 .await
                 .expect("add_task failed");
             let task_id = add_result.return_value();
+            assert_eq!(add_result.events.iter().count(), 1, "expected a TaskAdded event");
 
             // Get tasks
             let get_tasks = call_builder.get_tasks();
@@ -171,11 +447,12 @@ mod todo {
 
             // Complete task
             let complete = call_builder.complete_task(task_id);
-            let _complete_result = client
+            let complete_result = client
                 .call(&ink_e2e::alice(), &complete)
                 .submit()
                 .await
                 .expect("complete_task failed");
+            assert!(complete_result.return_value().is_ok());
 
             // Verify task is completed
             let get_task = call_builder.get_task(task_id);
@@ -184,5 +461,156 @@ mod todo {
 
             Ok(())
         }
+
+        /// Runs the shared test body against the in-process `drink!` sandbox: no node
+        /// process, no RPC, deterministic and fast.
+        #[ink_e2e::test(backend(runtime_only))]
+        async fn e2e_add_and_complete_task_sandbox<Client: E2EBackend>(
+            client: Client,
+        ) -> E2EResult<()> {
+            e2e_add_and_complete_task(client).await
+        }
+
+        /// Runs the shared test body against a real `substrate-contracts-node`, for
+        /// integration checks against the actual runtime.
+        #[ink_e2e::test]
+        async fn e2e_add_and_complete_task_node(
+            client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            e2e_add_and_complete_task(client).await
+        }
+
+        #[ink_e2e::test(backend(runtime_only))]
+        async fn e2e_set_code_is_admin_gated<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let mut constructor = TodoRef::new();
+            let contract = client
+                .instantiate("todo", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let code_hash = contract.code_hash;
+            let mut call_builder = contract.call_builder::<Todo>();
+
+            let add_task = call_builder.add_task(String::from("Survive the upgrade"));
+            client
+                .call(&ink_e2e::alice(), &add_task)
+                .submit()
+                .await
+                .expect("add_task failed");
+
+            // A non-admin cannot upgrade the contract.
+            let set_code = call_builder.set_code(code_hash);
+            let non_admin_result = client
+                .call(&ink_e2e::bob(), &set_code)
+                .dry_run()
+                .await
+                .expect("set_code dry run failed");
+            assert_eq!(non_admin_result.return_value(), Err(Error::NotAuthorized));
+
+            // The admin can upgrade (here, to its own code hash) without losing tasks.
+            let set_code = call_builder.set_code(code_hash);
+            let admin_result = client
+                .call(&ink_e2e::alice(), &set_code)
+                .submit()
+                .await
+                .expect("set_code failed");
+            assert!(admin_result.return_value().is_ok());
+
+            let get_tasks = call_builder.get_tasks();
+            let tasks_result = client.call(&ink_e2e::alice(), &get_tasks).dry_run().await?;
+            assert_eq!(tasks_result.return_value().len(), 1);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test(backend(runtime_only))]
+        async fn e2e_only_owner_or_admin_can_mutate_a_task<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let mut constructor = TodoRef::new();
+            let contract = client
+                .instantiate("todo", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<Todo>();
+
+            // Bob creates his own task.
+            let add_task = call_builder.add_task(String::from("Bob's task"));
+            let add_result = client
+                .call(&ink_e2e::bob(), &add_task)
+                .submit()
+                .await
+                .expect("add_task failed");
+            let task_id = add_result.return_value();
+
+            // Charlie, a random third party, may not touch Bob's task.
+            let complete = call_builder.complete_task(task_id);
+            let charlie_result = client
+                .call(&ink_e2e::charlie(), &complete)
+                .dry_run()
+                .await
+                .expect("complete_task dry run failed");
+            assert_eq!(charlie_result.return_value(), Err(Error::NotAuthorized));
+
+            // The admin (alice, who instantiated the contract) may act on Bob's behalf.
+            let complete = call_builder.complete_task(task_id);
+            let admin_result = client
+                .call(&ink_e2e::alice(), &complete)
+                .submit()
+                .await
+                .expect("complete_task failed");
+            assert!(admin_result.return_value().is_ok());
+
+            Ok(())
+        }
+
+        #[ink_e2e::test(backend(runtime_only))]
+        async fn e2e_only_owner_or_admin_can_remove_a_task<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let mut constructor = TodoRef::new();
+            let contract = client
+                .instantiate("todo", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<Todo>();
+
+            // Bob creates his own task.
+            let add_task = call_builder.add_task(String::from("Bob's task"));
+            let add_result = client
+                .call(&ink_e2e::bob(), &add_task)
+                .submit()
+                .await
+                .expect("add_task failed");
+            let task_id = add_result.return_value();
+
+            // Charlie, a random third party, may not remove Bob's task.
+            let remove = call_builder.remove_task(task_id);
+            let charlie_result = client
+                .call(&ink_e2e::charlie(), &remove)
+                .dry_run()
+                .await
+                .expect("remove_task dry run failed");
+            assert_eq!(charlie_result.return_value(), Err(Error::NotAuthorized));
+
+            // The admin (alice, who instantiated the contract) may remove it on Bob's behalf.
+            let remove = call_builder.remove_task(task_id);
+            let admin_result = client
+                .call(&ink_e2e::alice(), &remove)
+                .submit()
+                .await
+                .expect("remove_task failed");
+            assert!(admin_result.return_value().is_ok());
+
+            let get_tasks = call_builder.get_tasks();
+            let tasks_result = client.call(&ink_e2e::alice(), &get_tasks).dry_run().await?;
+            assert_eq!(tasks_result.return_value().len(), 0);
+
+            Ok(())
+        }
     }
 }
\ No newline at end of file